@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(feature = "rayon"), no_std)]
 
 //! This crate provides utility to unify [`Iterator`]s over the same type.
 //!
@@ -46,6 +46,8 @@
 //! You either have to define the enum yourself or use [auto_enum](https://crates.io/crates/auto_enums),
 //! which uses proc-macros.
 
+use core::iter::FusedIterator;
+
 macro_rules! impl_iter_enum {
     (
         $EnumId:ident,
@@ -68,6 +70,117 @@ macro_rules! impl_iter_enum {
                     $(Self::$A(inner) => inner.next()),*
                 }
             }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self {
+                    $(Self::$A(inner) => inner.size_hint()),*
+                }
+            }
+            fn count(self) -> usize {
+                match self {
+                    $(Self::$A(inner) => inner.count()),*
+                }
+            }
+            fn last(self) -> Option<Self::Item> {
+                match self {
+                    $(Self::$A(inner) => inner.last()),*
+                }
+            }
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                match self {
+                    $(Self::$A(inner) => inner.nth(n)),*
+                }
+            }
+            fn fold<Acc, Fold>(self, init: Acc, f: Fold) -> Acc
+            where
+                Fold: FnMut(Acc, Self::Item) -> Acc,
+            {
+                match self {
+                    $(Self::$A(inner) => inner.fold(init, f)),*
+                }
+            }
+        }
+
+        impl<I, $($A: DoubleEndedIterator<Item = I>),*> DoubleEndedIterator for $EnumId<$($A),*> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match self {
+                    $(Self::$A(inner) => inner.next_back()),*
+                }
+            }
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                match self {
+                    $(Self::$A(inner) => inner.nth_back(n)),*
+                }
+            }
+            fn rfold<Acc, Fold>(self, init: Acc, f: Fold) -> Acc
+            where
+                Fold: FnMut(Acc, Self::Item) -> Acc,
+            {
+                match self {
+                    $(Self::$A(inner) => inner.rfold(init, f)),*
+                }
+            }
+        }
+
+        impl<I, $($A: ExactSizeIterator<Item = I>),*> ExactSizeIterator for $EnumId<$($A),*> {
+            fn len(&self) -> usize {
+                match self {
+                    $(Self::$A(inner) => inner.len()),*
+                }
+            }
+        }
+
+        impl<I, $($A: FusedIterator<Item = I>),*> FusedIterator for $EnumId<$($A),*> {}
+
+        impl<T, $($A: Extend<T>),*> Extend<T> for $EnumId<$($A),*> {
+            fn extend<It: IntoIterator<Item = T>>(&mut self, it: It) {
+                match self {
+                    $(Self::$A(inner) => inner.extend(it)),*
+                }
+            }
+        }
+
+        #[cfg(feature = "rayon")]
+        impl<I: Send, $($A: rayon::iter::ParallelIterator<Item = I>),*>
+            rayon::iter::ParallelIterator for $EnumId<$($A),*>
+        {
+            type Item = I;
+            fn drive_unindexed<Cons>(self, consumer: Cons) -> Cons::Result
+            where
+                Cons: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+            {
+                match self {
+                    $(Self::$A(inner) => inner.drive_unindexed(consumer)),*
+                }
+            }
+            fn opt_len(&self) -> Option<usize> {
+                match self {
+                    $(Self::$A(inner) => inner.opt_len()),*
+                }
+            }
+        }
+
+        #[cfg(feature = "rayon")]
+        impl<I: Send, $($A: rayon::iter::IndexedParallelIterator<Item = I>),*>
+            rayon::iter::IndexedParallelIterator for $EnumId<$($A),*>
+        {
+            fn len(&self) -> usize {
+                match self {
+                    $(Self::$A(inner) => inner.len()),*
+                }
+            }
+            fn drive<Cons: rayon::iter::plumbing::Consumer<Self::Item>>(self, consumer: Cons) -> Cons::Result {
+                match self {
+                    $(Self::$A(inner) => inner.drive(consumer)),*
+                }
+            }
+            fn with_producer<CB: rayon::iter::plumbing::ProducerCallback<Self::Item>>(
+                self,
+                callback: CB,
+            ) -> CB::Output {
+                match self {
+                    $(Self::$A(inner) => inner.with_producer(callback)),*
+                }
+            }
         }
 
         pub trait $IntoTraitId: Sized {
@@ -127,6 +240,132 @@ impl_iter_enum!(
     (F, iter_enum_6f, (A, B, C, D, F), ()),
 );
 
+/// Select the right [`IterEnum2`]..[`IterEnum6`] for a `match` or `if`/`else`
+/// and wrap each arm in the correct variant automatically.
+///
+/// Writing `.iter_enum_3a()`, `.iter_enum_3b()`, ... by hand means counting the
+/// branches and picking the matching letter, and adding a branch forces a rename
+/// of every sibling. This macro does that bookkeeping: it counts the arms, picks
+/// `IterEnum{N}`, and expands arm *i* to `IterEnum{N}::{variant_i}(expr)`.
+///
+/// # Example
+///
+/// ```rust
+/// use iter_enumeration::iter_enum;
+///
+/// let it = iter_enum!(match 42 {
+///     0 => 0..1,
+///     1 => (0..3).map(|i| i + 1),
+///     _ => (0..4).filter(|i| i % 2 == 0),
+/// });
+/// assert_eq!(it.count(), 2);
+///
+/// let it = iter_enum!(if true { 0..10 } else { (0..10).filter(|i| i % 2 == 0) });
+/// assert_eq!(it.count(), 10);
+/// ```
+#[macro_export]
+macro_rules! iter_enum {
+    // Public `if`/`else` entry. The condition is gathered by TT-munching because
+    // an `expr` fragment cannot be directly followed by a `{`.
+    (if $($rest:tt)*) => {
+        $crate::iter_enum!(@if [] $($rest)*)
+    };
+    // Public `match` entry; the scrutinee is gathered the same way.
+    (match $($rest:tt)*) => {
+        $crate::iter_enum!(@match [] $($rest)*)
+    };
+
+    // `if`/`else`: once the whole condition has been collected the remainder is a
+    // single `{ .. } else { .. }` shape.
+    (@if [$($cond:tt)*] { $a:expr } else { $b:expr }) => {
+        if $($cond)* {
+            $crate::IterEnum2::A($a)
+        } else {
+            $crate::IterEnum2::B($b)
+        }
+    };
+    (@if [$($cond:tt)*] $head:tt $($rest:tt)*) => {
+        $crate::iter_enum!(@if [$($cond)* $head] $($rest)*)
+    };
+
+    // `match`: once the scrutinee has been collected the remainder is the brace
+    // group of arms, which we forward to the arm-counting rules.
+    (@match [$($scrutinee:tt)*] { $($arms:tt)* }) => {
+        $crate::iter_enum!(@arms ($($scrutinee)*) $($arms)*)
+    };
+    (@match [$($scrutinee:tt)*] $head:tt $($rest:tt)*) => {
+        $crate::iter_enum!(@match [$($scrutinee)* $head] $($rest)*)
+    };
+
+    // Arm-counting rules: one per supported width, picking `IterEnum{N}` and
+    // wrapping arm *i* in its variant.
+    (@arms ($($scrutinee:tt)*)
+        $pa:pat => $ea:expr,
+        $pb:pat => $eb:expr $(,)?
+    ) => {
+        match $($scrutinee)* {
+            $pa => $crate::IterEnum2::A($ea),
+            $pb => $crate::IterEnum2::B($eb),
+        }
+    };
+    (@arms ($($scrutinee:tt)*)
+        $pa:pat => $ea:expr,
+        $pb:pat => $eb:expr,
+        $pc:pat => $ec:expr $(,)?
+    ) => {
+        match $($scrutinee)* {
+            $pa => $crate::IterEnum3::A($ea),
+            $pb => $crate::IterEnum3::B($eb),
+            $pc => $crate::IterEnum3::C($ec),
+        }
+    };
+    (@arms ($($scrutinee:tt)*)
+        $pa:pat => $ea:expr,
+        $pb:pat => $eb:expr,
+        $pc:pat => $ec:expr,
+        $pd:pat => $ed:expr $(,)?
+    ) => {
+        match $($scrutinee)* {
+            $pa => $crate::IterEnum4::A($ea),
+            $pb => $crate::IterEnum4::B($eb),
+            $pc => $crate::IterEnum4::C($ec),
+            $pd => $crate::IterEnum4::D($ed),
+        }
+    };
+    (@arms ($($scrutinee:tt)*)
+        $pa:pat => $ea:expr,
+        $pb:pat => $eb:expr,
+        $pc:pat => $ec:expr,
+        $pd:pat => $ed:expr,
+        $pe:pat => $ee:expr $(,)?
+    ) => {
+        match $($scrutinee)* {
+            $pa => $crate::IterEnum5::A($ea),
+            $pb => $crate::IterEnum5::B($eb),
+            $pc => $crate::IterEnum5::C($ec),
+            $pd => $crate::IterEnum5::D($ed),
+            $pe => $crate::IterEnum5::E($ee),
+        }
+    };
+    (@arms ($($scrutinee:tt)*)
+        $pa:pat => $ea:expr,
+        $pb:pat => $eb:expr,
+        $pc:pat => $ec:expr,
+        $pd:pat => $ed:expr,
+        $pe:pat => $ee:expr,
+        $pf:pat => $ef:expr $(,)?
+    ) => {
+        match $($scrutinee)* {
+            $pa => $crate::IterEnum6::A($ea),
+            $pb => $crate::IterEnum6::B($eb),
+            $pc => $crate::IterEnum6::C($ec),
+            $pd => $crate::IterEnum6::D($ed),
+            $pe => $crate::IterEnum6::E($ee),
+            $pf => $crate::IterEnum6::F($ef),
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use core::iter::{empty, once};
@@ -164,6 +403,34 @@ mod tests {
         assert!(eval(42) == 4);
     }
 
+    #[test]
+    fn declarative_macro() {
+        let eval = |i| {
+            iter_enum!(match i {
+                0 => 0..1,
+                1 => 0..3,
+                _ => 0..4,
+            })
+            .count()
+        };
+
+        assert!(eval(0) == 1);
+        assert!(eval(1) == 3);
+        assert!(eval(42) == 4);
+
+        let eval = |b| {
+            iter_enum!(if b {
+                0..10
+            } else {
+                (0..10).filter(|i| i % 2 == 0)
+            })
+            .count()
+        };
+
+        assert!(eval(true) == 10);
+        assert!(eval(false) == 5);
+    }
+
     #[test]
     fn iterator_of_iterators() {
         assert!(